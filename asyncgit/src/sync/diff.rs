@@ -0,0 +1,34 @@
+///
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum DiffLineType {
+    ///
+    Header,
+    ///
+    Add,
+    ///
+    Delete,
+    ///
+    None,
+}
+
+impl Default for DiffLineType {
+    fn default() -> Self {
+        Self::None
+    }
+}
+
+///
+#[derive(Default, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct DiffLine {
+    ///
+    pub content: String,
+    ///
+    pub line_type: DiffLineType,
+}
+
+///
+#[derive(Default, Clone, Debug)]
+pub struct Diff {
+    ///
+    pub lines: Vec<DiffLine>,
+}
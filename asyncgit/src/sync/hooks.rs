@@ -9,6 +9,52 @@ use tempfile::NamedTempFile;
 
 const HOOK_POST_COMMIT: &str = ".git/hooks/post-commit";
 const HOOK_COMMIT_MSG: &str = ".git/hooks/commit-msg";
+const HOOK_PRE_COMMIT: &str = ".git/hooks/pre-commit";
+const HOOK_PREPARE_COMMIT_MSG: &str =
+    ".git/hooks/prepare-commit-msg";
+
+///
+pub fn hooks_pre_commit(repo_path: &str) -> HookResult {
+    scope_time!("hooks_pre_commit");
+
+    if hook_runable(repo_path, HOOK_PRE_COMMIT) {
+        run_hook(repo_path, HOOK_PRE_COMMIT, &[])
+    } else {
+        HookResult::Ok
+    }
+}
+
+///
+pub fn hooks_prepare_commit_msg(
+    repo_path: &str,
+    msg: &mut String,
+    source: &str,
+) -> HookResult {
+    scope_time!("hooks_prepare_commit_msg");
+
+    if hook_runable(repo_path, HOOK_PREPARE_COMMIT_MSG) {
+        let mut file = NamedTempFile::new().unwrap();
+
+        write!(file, "{}", msg).unwrap();
+
+        let file_path = file.path().to_str().unwrap();
+
+        let res = run_hook(
+            repo_path,
+            HOOK_PREPARE_COMMIT_MSG,
+            &[&file_path, source],
+        );
+
+        // load possibly altered msg
+        let mut file = file.reopen().unwrap();
+        msg.clear();
+        file.read_to_string(msg).unwrap();
+
+        res
+    } else {
+        HookResult::Ok
+    }
+}
 
 ///
 pub fn hooks_commit_msg(
@@ -165,6 +211,52 @@ exit 1
         assert_eq!(msg, String::from("msg\n"));
     }
 
+    #[test]
+    #[cfg(not(windows))]
+    fn test_hooks_pre_commit() {
+        let (_td, repo) = repo_init();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let hook = b"
+#!/bin/sh
+echo 'rejected'
+exit 1
+        ";
+
+        create_hook(root, HOOK_PRE_COMMIT, hook);
+
+        let res = hooks_pre_commit(repo_path);
+
+        assert_eq!(
+            res,
+            HookResult::NotOk(String::from("rejected\n"))
+        );
+    }
+
+    #[test]
+    #[cfg(not(windows))]
+    fn test_hooks_prepare_commit_msg() {
+        let (_td, repo) = repo_init();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let hook = b"
+#!/bin/sh
+echo \"msg $2\" > $1
+        ";
+
+        create_hook(root, HOOK_PREPARE_COMMIT_MSG, hook);
+
+        let mut msg = String::from("test");
+        let res = hooks_prepare_commit_msg(
+            repo_path, &mut msg, "message",
+        );
+
+        assert_eq!(res, HookResult::Ok);
+        assert_eq!(msg, String::from("msg message\n"));
+    }
+
     #[test]
     #[cfg(not(windows))]
     fn test_commit_msg_no_block_but_alter() {
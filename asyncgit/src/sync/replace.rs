@@ -0,0 +1,126 @@
+use regex::Regex;
+use scopetime::scope_time;
+use std::{fs, path::PathBuf};
+
+use super::utils::repo;
+
+///
+#[derive(Debug, Clone)]
+pub struct ReplaceResult {
+    ///
+    pub path: PathBuf,
+    ///
+    pub old_content: String,
+    ///
+    pub new_content: String,
+}
+
+/// walks all tracked files in `repo_path`, applies `pattern` ->
+/// `replacement` in-memory and returns one [`ReplaceResult`] per
+/// file whose content actually changed. Nothing is written to disk.
+pub fn replace(
+    repo_path: &str,
+    pattern: &str,
+    replacement: &str,
+) -> Result<Vec<ReplaceResult>, regex::Error> {
+    scope_time!("replace");
+
+    let re = Regex::new(pattern)?;
+    let repo = repo(repo_path);
+    let mut results = Vec::new();
+
+    let index = repo.index().expect("index error");
+
+    for entry in index.iter() {
+        let rel_path = String::from_utf8_lossy(&entry.path)
+            .to_string();
+        let full_path = repo.path()
+            .parent()
+            .expect("repo root")
+            .join(&rel_path);
+
+        let old_content = match fs::read_to_string(&full_path) {
+            Ok(c) => c,
+            // binary/non-utf8 files are left untouched
+            Err(_) => continue,
+        };
+
+        let new_content =
+            re.replace_all(&old_content, replacement).to_string();
+
+        if new_content != old_content {
+            results.push(ReplaceResult {
+                path: PathBuf::from(rel_path),
+                old_content,
+                new_content,
+            });
+        }
+    }
+
+    Ok(results)
+}
+
+/// writes `new_content` for `path` (relative to `repo_path`) back
+/// to the working tree, ready to be staged with `stage_add`
+pub fn apply_replace(
+    repo_path: &str,
+    path: &PathBuf,
+    new_content: &str,
+) -> std::io::Result<()> {
+    scope_time!("apply_replace");
+
+    let full_path = PathBuf::from(repo_path).join(path);
+    fs::write(full_path, new_content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::tests::repo_init;
+    use std::fs::File;
+    use std::io::Write as _;
+
+    fn stage_file(
+        root: &std::path::Path,
+        rel: &str,
+        content: &str,
+    ) {
+        File::create(&root.join(rel))
+            .unwrap()
+            .write_all(content.as_bytes())
+            .unwrap();
+
+        let repo = repo(root.as_os_str().to_str().unwrap());
+        let mut index = repo.index().unwrap();
+        index.add_path(std::path::Path::new(rel)).unwrap();
+        index.write().unwrap();
+    }
+
+    #[test]
+    fn test_replace_changes_matching_files() {
+        let (_td, repo) = repo_init();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        stage_file(root, "foo.txt", "hello world\n");
+
+        let results =
+            replace(repo_path, "world", "there").unwrap();
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].new_content, "hello there\n");
+    }
+
+    #[test]
+    fn test_replace_no_match_returns_empty() {
+        let (_td, repo) = repo_init();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        stage_file(root, "foo.txt", "hello world\n");
+
+        let results = replace(repo_path, "nomatch", "x").unwrap();
+
+        assert!(results.is_empty());
+    }
+}
@@ -0,0 +1,92 @@
+use super::utils::repo;
+use scopetime::scope_time;
+
+/// snapshot of the repo's tracking state relative to its upstream,
+/// cheap enough to recompute but expensive enough (ahead/behind
+/// walks large repos) that it belongs off the render thread
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct RepoState {
+    ///
+    pub branch: Option<String>,
+    ///
+    pub upstream: Option<String>,
+    ///
+    pub ahead: usize,
+    ///
+    pub behind: usize,
+    ///
+    pub is_dirty: bool,
+}
+
+///
+pub fn repo_state(repo_path: &str) -> RepoState {
+    scope_time!("repo_state");
+
+    let repo = repo(repo_path);
+
+    let head = match repo.head() {
+        Ok(head) => head,
+        Err(_) => return RepoState::default(),
+    };
+
+    let branch = head.shorthand().map(String::from);
+    let head_oid = head.target();
+
+    let mut state = RepoState {
+        branch,
+        is_dirty: !repo
+            .statuses(None)
+            .map(|s| s.is_empty())
+            .unwrap_or(true),
+        ..RepoState::default()
+    };
+
+    if let Some(branch_name) = state.branch.clone() {
+        if let Ok(local_branch) = repo.find_branch(
+            &branch_name,
+            git2::BranchType::Local,
+        ) {
+            if let Ok(upstream) = local_branch.upstream() {
+                state.upstream = upstream
+                    .name()
+                    .ok()
+                    .flatten()
+                    .map(String::from);
+
+                if let (Some(local_oid), Some(upstream_oid)) = (
+                    head_oid,
+                    upstream.get().target(),
+                ) {
+                    if let Ok((ahead, behind)) = repo
+                        .graph_ahead_behind(local_oid, upstream_oid)
+                    {
+                        state.ahead = ahead;
+                        state.behind = behind;
+                    }
+                }
+            }
+        }
+    }
+
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::tests::repo_init;
+
+    #[test]
+    fn test_branch_without_upstream() {
+        let (_td, repo) = repo_init();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let state = repo_state(repo_path);
+
+        assert!(state.branch.is_some());
+        assert_eq!(state.upstream, None);
+        assert_eq!(state.ahead, 0);
+        assert_eq!(state.behind, 0);
+    }
+}
@@ -0,0 +1,312 @@
+use super::diff::{Diff, DiffLine, DiffLineType};
+
+const CONTEXT_LINES: usize = 3;
+
+///
+#[derive(Debug, Clone)]
+pub struct Hunk {
+    ///
+    pub old_start: usize,
+    ///
+    pub old_lines: usize,
+    ///
+    pub new_start: usize,
+    ///
+    pub new_lines: usize,
+    ///
+    pub diff: Diff,
+}
+
+// the LCS table below is O(n*m) time *and* memory; above this many
+// cells we skip the minimal diff entirely and report the whole
+// trimmed middle as replaced rather than hang or OOM on a large
+// generated file (a lockfile, a bundled asset, ...)
+const MAX_DIFF_CELLS: usize = 4_000_000;
+
+/// produces a minimal unified diff between `old` and `new`, grouping
+/// changed lines into hunks with a few lines of surrounding context;
+/// when the changed middle exceeds `MAX_DIFF_CELLS` it is reported
+/// as fully deleted and re-inserted instead of minimally diffed
+pub fn unified_diff(old: &str, new: &str) -> Vec<Hunk> {
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+
+    let ops = diff_ops_trimmed(&old_lines, &new_lines);
+
+    group_into_hunks(&old_lines, &new_lines, &ops)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Op {
+    Equal(usize, usize),
+    Delete(usize),
+    Insert(usize),
+}
+
+// strips the common prefix/suffix shared by `old` and `new` (the
+// common case of a small in-file change leaves barely anything in
+// the middle, however large the file is) before diffing, and falls
+// back to a flat replace of the remaining middle when it's still too
+// big to run the quadratic LCS over
+fn diff_ops_trimmed(old: &[&str], new: &[&str]) -> Vec<Op> {
+    let n = old.len();
+    let m = new.len();
+
+    let mut prefix = 0;
+    while prefix < n && prefix < m && old[prefix] == new[prefix] {
+        prefix += 1;
+    }
+
+    let mut suffix = 0;
+    while suffix < n - prefix
+        && suffix < m - prefix
+        && old[n - 1 - suffix] == new[m - 1 - suffix]
+    {
+        suffix += 1;
+    }
+
+    let mid_old = &old[prefix..n - suffix];
+    let mid_new = &new[prefix..m - suffix];
+
+    let mut ops = Vec::with_capacity(
+        prefix + suffix + mid_old.len() + mid_new.len(),
+    );
+
+    for i in 0..prefix {
+        ops.push(Op::Equal(i, i));
+    }
+
+    if mid_old.len().saturating_mul(mid_new.len()) > MAX_DIFF_CELLS {
+        for k in 0..mid_old.len() {
+            ops.push(Op::Delete(prefix + k));
+        }
+        for k in 0..mid_new.len() {
+            ops.push(Op::Insert(prefix + k));
+        }
+    } else {
+        for op in diff_ops(mid_old, mid_new) {
+            ops.push(match op {
+                Op::Equal(i, j) => {
+                    Op::Equal(prefix + i, prefix + j)
+                }
+                Op::Delete(i) => Op::Delete(prefix + i),
+                Op::Insert(j) => Op::Insert(prefix + j),
+            });
+        }
+    }
+
+    for k in 0..suffix {
+        ops.push(Op::Equal(n - suffix + k, m - suffix + k));
+    }
+
+    ops
+}
+
+// a small LCS-based line diff; only ever called on the trimmed
+// middle left by `diff_ops_trimmed`, and only when that middle is
+// at most `MAX_DIFF_CELLS` cells
+fn diff_ops(old: &[&str], new: &[&str]) -> Vec<Op> {
+    let n = old.len();
+    let m = new.len();
+    let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut ops = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if old[i] == new[j] {
+            ops.push(Op::Equal(i, j));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            ops.push(Op::Delete(i));
+            i += 1;
+        } else {
+            ops.push(Op::Insert(j));
+            j += 1;
+        }
+    }
+    while i < n {
+        ops.push(Op::Delete(i));
+        i += 1;
+    }
+    while j < m {
+        ops.push(Op::Insert(j));
+        j += 1;
+    }
+
+    ops
+}
+
+// maximal contiguous runs of non-`Equal` ops, as `[start, end)`
+// ranges into `ops`
+fn change_runs(ops: &[Op]) -> Vec<(usize, usize)> {
+    let mut runs = Vec::new();
+    let mut i = 0;
+
+    while i < ops.len() {
+        if matches!(ops[i], Op::Equal(..)) {
+            i += 1;
+            continue;
+        }
+
+        let start = i;
+        while i < ops.len() && !matches!(ops[i], Op::Equal(..)) {
+            i += 1;
+        }
+        runs.push((start, i));
+    }
+
+    runs
+}
+
+fn group_into_hunks(
+    old: &[&str],
+    new: &[&str],
+    ops: &[Op],
+) -> Vec<Hunk> {
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+
+    for (start, end) in change_runs(ops) {
+        let hunk_start = start.saturating_sub(CONTEXT_LINES);
+        let hunk_end = (end + CONTEXT_LINES).min(ops.len());
+
+        match ranges.last_mut() {
+            // two change runs within `2 * CONTEXT_LINES` of each
+            // other share their surrounding context in one hunk
+            Some(last) if hunk_start <= last.1 => {
+                last.1 = hunk_end;
+            }
+            _ => ranges.push((hunk_start, hunk_end)),
+        }
+    }
+
+    ranges
+        .into_iter()
+        .map(|(start, end)| build_hunk(old, new, &ops[start..end]))
+        .collect()
+}
+
+fn build_hunk(old: &[&str], new: &[&str], slice: &[Op]) -> Hunk {
+    let diff_lines: Vec<DiffLine> = slice
+        .iter()
+        .map(|op| match *op {
+            Op::Equal(oi, _) => DiffLine {
+                content: format!(" {}", old[oi]),
+                line_type: DiffLineType::None,
+            },
+            Op::Delete(oi) => DiffLine {
+                content: format!("-{}", old[oi]),
+                line_type: DiffLineType::Delete,
+            },
+            Op::Insert(ni) => DiffLine {
+                content: format!("+{}", new[ni]),
+                line_type: DiffLineType::Add,
+            },
+        })
+        .collect();
+
+    let old_start = slice
+        .iter()
+        .find_map(|op| match op {
+            Op::Equal(oi, _) | Op::Delete(oi) => Some(*oi),
+            _ => None,
+        })
+        .unwrap_or(0);
+    let new_start = slice
+        .iter()
+        .find_map(|op| match op {
+            Op::Equal(_, ni) | Op::Insert(ni) => Some(*ni),
+            _ => None,
+        })
+        .unwrap_or(0);
+
+    Hunk {
+        old_start,
+        old_lines: slice
+            .iter()
+            .filter(|op| !matches!(op, Op::Insert(_)))
+            .count(),
+        new_start,
+        new_lines: slice
+            .iter()
+            .filter(|op| !matches!(op, Op::Delete(_)))
+            .count(),
+        diff: Diff { lines: diff_lines },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_single_line_change_is_one_hunk() {
+        let hunks = unified_diff("a\nb\nc\nd\ne", "a\nb\nX\nd\ne");
+
+        assert_eq!(hunks.len(), 1);
+        assert_eq!(
+            hunks[0]
+                .diff
+                .lines
+                .iter()
+                .filter(|l| l.line_type == DiffLineType::Delete)
+                .count(),
+            1
+        );
+        assert_eq!(
+            hunks[0]
+                .diff
+                .lines
+                .iter()
+                .filter(|l| l.line_type == DiffLineType::Add)
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_identical_content_has_no_hunks() {
+        assert!(unified_diff("same\ntext\n", "same\ntext\n").is_empty());
+    }
+
+    #[test]
+    fn test_two_distant_changes_are_two_hunks() {
+        let old = "a\nb\nc\nd\ne\nf\ng\nh\ni\nj";
+        let new = "a\nX\nc\nd\ne\nf\ng\nh\ni\nY";
+
+        let hunks = unified_diff(old, new);
+
+        assert_eq!(hunks.len(), 2);
+    }
+
+    #[test]
+    fn test_oversized_middle_falls_back_to_replace_without_hanging()
+    {
+        // no shared prefix/suffix and large enough that the LCS
+        // table would blow past `MAX_DIFF_CELLS` - must still
+        // return promptly with a single hunk covering everything
+        let old: Vec<String> =
+            (0..3000).map(|i| format!("old-line-{}", i)).collect();
+        let new: Vec<String> =
+            (0..3000).map(|i| format!("new-line-{}", i)).collect();
+
+        let hunks = unified_diff(
+            &old.join("\n"),
+            &new.join("\n"),
+        );
+
+        assert_eq!(hunks.len(), 1);
+        assert_eq!(hunks[0].old_lines, 3000);
+        assert_eq!(hunks[0].new_lines, 3000);
+    }
+}
@@ -0,0 +1,66 @@
+use super::DrawableComponent;
+use asyncgit::sync::state::RepoState;
+use std::borrow::Cow;
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    style::{Color, Style},
+    widgets::{Paragraph, Text, Widget},
+    Frame,
+};
+
+/// shows the current branch plus its ahead/behind indicator,
+/// refreshed out-of-band by `AsyncGitInfo`
+pub struct BranchStatusComponent {
+    state: RepoState,
+}
+
+impl BranchStatusComponent {
+    ///
+    pub fn new() -> Self {
+        Self {
+            state: RepoState::default(),
+        }
+    }
+
+    ///
+    pub fn update(&mut self, state: RepoState) {
+        self.state = state;
+    }
+
+    fn text(&self) -> String {
+        let branch =
+            self.state.branch.as_deref().unwrap_or("HEAD");
+
+        if self.state.upstream.is_none() {
+            return format!("{} (no upstream)", branch);
+        }
+
+        format!(
+            "{} \u{2191}{} \u{2193}{}{}",
+            branch,
+            self.state.ahead,
+            self.state.behind,
+            if self.state.is_dirty { " *" } else { "" }
+        )
+    }
+}
+
+impl Default for BranchStatusComponent {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DrawableComponent for BranchStatusComponent {
+    fn draw<B: Backend>(&self, f: &mut Frame<B>, r: Rect) {
+        Paragraph::new(
+            [Text::Styled(
+                Cow::from(self.text()),
+                Style::default().fg(Color::White),
+            )]
+            .iter(),
+        )
+        .render(f, r);
+    }
+}
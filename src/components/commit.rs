@@ -8,11 +8,24 @@ use crate::{
     strings, ui,
 };
 use asyncgit::{sync, CWD};
-use crossterm::event::{Event, KeyCode};
+use crossterm::{
+    event::{Event, KeyCode},
+    execute,
+    terminal::{
+        disable_raw_mode, enable_raw_mode, EnterAlternateScreen,
+        LeaveAlternateScreen,
+    },
+};
 use log::error;
-use std::borrow::Cow;
+use std::{
+    borrow::Cow,
+    env, fs,
+    io::{self, Write},
+    process::Command,
+};
 use strings::commands;
 use sync::HookResult;
+use tempfile::NamedTempFile;
 use tui::{
     backend::Backend,
     layout::{Alignment, Rect},
@@ -32,12 +45,17 @@ impl DrawableComponent for CommitComponent {
     fn draw<B: Backend>(&self, f: &mut Frame<B>, _rect: Rect) {
         if self.visible {
             let txt = if self.msg.is_empty() {
-                [Text::Styled(
+                vec![Text::Styled(
                     Cow::from(strings::COMMIT_MSG),
                     Style::default().fg(Color::DarkGray),
                 )]
             } else {
-                [Text::Raw(Cow::from(self.msg.clone()))]
+                self.msg
+                    .lines()
+                    .map(|line| {
+                        Text::Raw(Cow::from(format!("{}\n", line)))
+                    })
+                    .collect()
             };
 
             ui::Clear::new(
@@ -47,7 +65,8 @@ impl DrawableComponent for CommitComponent {
                             .title(strings::COMMIT_TITLE)
                             .borders(Borders::ALL),
                     )
-                    .alignment(Alignment::Left),
+                    .alignment(Alignment::Left)
+                    .wrap(true),
             )
             .render(f, ui::centered_rect(60, 20, f.size()));
         }
@@ -81,6 +100,16 @@ impl Component for CommitComponent {
     fn event(&mut self, ev: Event) -> bool {
         if self.visible {
             if let Event::Key(e) = ev {
+                if let keys::COMMIT_CONFIRM = e {
+                    if self.can_commit() {
+                        self.commit();
+                    }
+                    return true;
+                }
+                if let keys::COMMIT_OPEN_EDITOR = e {
+                    self.open_editor();
+                    return true;
+                }
                 match e.code {
                     KeyCode::Esc => {
                         self.hide();
@@ -88,8 +117,8 @@ impl Component for CommitComponent {
                     KeyCode::Char(c) => {
                         self.msg.push(c);
                     }
-                    KeyCode::Enter if self.can_commit() => {
-                        self.commit();
+                    KeyCode::Enter => {
+                        self.msg.push('\n');
                     }
                     KeyCode::Backspace if !self.msg.is_empty() => {
                         self.msg.pop().unwrap();
@@ -134,6 +163,32 @@ impl CommitComponent {
     }
 
     fn commit(&mut self) {
+        if let HookResult::NotOk(e) = sync::hooks_pre_commit(CWD) {
+            error!("pre-commit hook error: {}", e);
+            self.queue.borrow_mut().push_back(
+                InternalEvent::ShowMsg(format!(
+                    "pre-commit hook error:\n{}",
+                    e
+                )),
+            );
+            return;
+        }
+
+        if let HookResult::NotOk(e) = sync::hooks_prepare_commit_msg(
+            CWD,
+            &mut self.msg,
+            "message",
+        ) {
+            error!("prepare-commit-msg hook error: {}", e);
+            self.queue.borrow_mut().push_back(
+                InternalEvent::ShowMsg(format!(
+                    "prepare-commit-msg hook error:\n{}",
+                    e
+                )),
+            );
+            return;
+        }
+
         if let HookResult::NotOk(e) =
             sync::hooks_commit_msg(CWD, &mut self.msg)
         {
@@ -166,6 +221,52 @@ impl CommitComponent {
             .push_back(InternalEvent::Update(NeedsUpdate::ALL));
     }
 
+    fn open_editor(&mut self) {
+        if let Err(e) = self.edit_msg_in_external_editor() {
+            error!("external editor error: {}", e);
+            self.queue.borrow_mut().push_back(
+                InternalEvent::ShowMsg(format!(
+                    "failed to launch editor:\n{}",
+                    e
+                )),
+            );
+        }
+    }
+
+    fn edit_msg_in_external_editor(&mut self) -> io::Result<()> {
+        let mut file = NamedTempFile::new()?;
+        write!(file, "{}", self.msg)?;
+        let file_path = file.path().to_path_buf();
+
+        let editor = env::var("GIT_EDITOR")
+            .or_else(|_| env::var("EDITOR"))
+            .unwrap_or_else(|_| String::from("vi"));
+
+        execute!(io::stdout(), LeaveAlternateScreen)?;
+        disable_raw_mode()?;
+
+        let res = Command::new(&editor).arg(&file_path).status();
+
+        enable_raw_mode()?;
+        execute!(io::stdout(), EnterAlternateScreen)?;
+
+        let status = res?;
+
+        // the user aborted the edit (`:cq` in vim, editor killed,
+        // ...) - keep the previous message rather than overwrite it
+        // with whatever half-written content is left in the tempfile
+        if !status.success() {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                format!("editor exited with {}", status),
+            ));
+        }
+
+        self.msg = fs::read_to_string(&file_path)?;
+
+        Ok(())
+    }
+
     fn can_commit(&self) -> bool {
         !self.msg.is_empty()
     }
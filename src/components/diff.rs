@@ -0,0 +1,80 @@
+use super::{syntax_text::SyntaxHighlighter, DrawableComponent};
+use asyncgit::{
+    hash,
+    sync::diff::{Diff, DiffLine},
+    StatusItem,
+};
+use std::borrow::Cow;
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    widgets::{Block, Borders, Paragraph, Text, Widget},
+    Frame,
+};
+
+///
+pub struct DiffComponent {
+    diff: Diff,
+    item: Option<StatusItem>,
+    highlighter: SyntaxHighlighter,
+    focused: bool,
+}
+
+impl DiffComponent {
+    ///
+    pub fn new(highlight_syntax: bool) -> Self {
+        Self {
+            diff: Diff::default(),
+            item: None,
+            highlighter: SyntaxHighlighter::new(highlight_syntax),
+            focused: false,
+        }
+    }
+
+    ///
+    pub fn update(&mut self, item: StatusItem, diff: Diff) {
+        if self.item.as_ref().map(|i| &i.path) != Some(&item.path)
+            || hash(&self.diff.lines) != hash(&diff.lines)
+        {
+            self.diff = diff;
+            self.item = Some(item);
+        }
+    }
+
+    fn styled_lines(&self) -> Vec<Vec<Text<'static>>> {
+        match &self.item {
+            Some(item) => self.highlighter.highlight(
+                &item.path,
+                hash(&self.diff.lines),
+                &self.diff.lines,
+            ),
+            None => Vec::new(),
+        }
+    }
+}
+
+fn flatten_lines(
+    lines: Vec<Vec<Text<'static>>>,
+) -> Vec<Text<'static>> {
+    lines
+        .into_iter()
+        .flat_map(|mut spans| {
+            spans.push(Text::Raw(Cow::from("\n")));
+            spans
+        })
+        .collect()
+}
+
+impl DrawableComponent for DiffComponent {
+    fn draw<B: Backend>(&self, f: &mut Frame<B>, r: Rect) {
+        let txt = flatten_lines(self.styled_lines());
+
+        Paragraph::new(txt.iter())
+            .block(
+                Block::default()
+                    .title("Diff")
+                    .borders(Borders::ALL),
+            )
+            .render(f, r);
+    }
+}
@@ -0,0 +1,409 @@
+use super::{
+    visibility_blocking, CommandBlocking, CommandInfo, Component,
+    DrawableComponent,
+};
+use crate::{
+    keys,
+    queue::{InternalEvent, NeedsUpdate, Queue},
+    strings, ui,
+};
+use asyncgit::{
+    sync::{
+        self,
+        replace::{apply_replace, replace, ReplaceResult},
+        unified_diff::{unified_diff, Hunk},
+    },
+    CWD,
+};
+use crossterm::event::{Event, KeyCode};
+use log::error;
+use std::{borrow::Cow, path::Path};
+use strings::commands;
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    style::{Color, Style},
+    widgets::{Text, Widget},
+    Frame,
+};
+
+struct FileHunks {
+    result: ReplaceResult,
+    hunks: Vec<Hunk>,
+    // per-hunk accept(true)/reject(false) decision; `None` while
+    // still undecided
+    decisions: Vec<Option<bool>>,
+}
+
+impl FileHunks {
+    fn new(result: ReplaceResult, hunks: Vec<Hunk>) -> Self {
+        let decisions = vec![None; hunks.len()];
+        Self {
+            result,
+            hunks,
+            decisions,
+        }
+    }
+
+    fn all_decided(&self) -> bool {
+        self.decisions.iter().all(Option::is_some)
+    }
+
+    // splices each hunk's old or new side back into the original
+    // file depending on its decision, leaving the rest of the file
+    // (the parts no hunk touched) untouched
+    fn resolved_content(&self) -> String {
+        let old_lines: Vec<&str> =
+            self.result.old_content.lines().collect();
+        let new_lines: Vec<&str> =
+            self.result.new_content.lines().collect();
+
+        let mut out: Vec<&str> = Vec::new();
+        let mut last_old = 0;
+
+        for (hunk, decision) in
+            self.hunks.iter().zip(&self.decisions)
+        {
+            out.extend_from_slice(
+                &old_lines[last_old..hunk.old_start],
+            );
+
+            if decision.unwrap_or(false) {
+                out.extend_from_slice(
+                    &new_lines[hunk.new_start
+                        ..hunk.new_start + hunk.new_lines],
+                );
+            } else {
+                out.extend_from_slice(
+                    &old_lines[hunk.old_start
+                        ..hunk.old_start + hunk.old_lines],
+                );
+            }
+
+            last_old = hunk.old_start + hunk.old_lines;
+        }
+
+        out.extend_from_slice(&old_lines[last_old..]);
+
+        let mut content = out.join("\n");
+        if self.result.old_content.ends_with('\n') {
+            content.push('\n');
+        }
+        content
+    }
+}
+
+///
+pub struct ReplaceComponent {
+    pattern: String,
+    replacement: String,
+    editing_replacement: bool,
+    files: Vec<FileHunks>,
+    selection: Option<(usize, usize)>,
+    visible: bool,
+    queue: Queue,
+}
+
+impl ReplaceComponent {
+    ///
+    pub fn new(queue: Queue) -> Self {
+        Self {
+            pattern: String::new(),
+            replacement: String::new(),
+            editing_replacement: false,
+            files: Vec::new(),
+            selection: None,
+            visible: false,
+            queue,
+        }
+    }
+
+    fn run_search(&mut self) {
+        match replace(CWD, &self.pattern, &self.replacement) {
+            Ok(results) => {
+                self.files = results
+                    .into_iter()
+                    .map(|result| {
+                        let hunks = unified_diff(
+                            &result.old_content,
+                            &result.new_content,
+                        );
+                        FileHunks::new(result, hunks)
+                    })
+                    .filter(|f| !f.hunks.is_empty())
+                    .collect();
+
+                self.selection = if self.files.is_empty() {
+                    None
+                } else {
+                    Some((0, 0))
+                };
+            }
+            Err(e) => {
+                error!("replace pattern error: {}", e);
+                self.queue.borrow_mut().push_back(
+                    InternalEvent::ShowMsg(format!(
+                        "invalid pattern: {}",
+                        e
+                    )),
+                );
+            }
+        }
+    }
+
+    fn accept_selected(&mut self) {
+        self.decide_selected(true);
+    }
+
+    fn reject_selected(&mut self) {
+        self.decide_selected(false);
+    }
+
+    // records accept/reject for the currently selected hunk; once
+    // every hunk in the file has a decision the file is written and
+    // staged, otherwise selection moves on to the next open hunk
+    fn decide_selected(&mut self, accept: bool) {
+        if let Some((file_idx, hunk_idx)) = self.selection {
+            let all_decided = if let Some(file) =
+                self.files.get_mut(file_idx)
+            {
+                if let Some(decision) =
+                    file.decisions.get_mut(hunk_idx)
+                {
+                    *decision = Some(accept);
+                }
+
+                file.all_decided()
+            } else {
+                return;
+            };
+
+            if all_decided {
+                self.finalize_file(file_idx);
+            } else {
+                self.select_next_undecided_hunk(file_idx);
+            }
+        }
+    }
+
+    fn select_next_undecided_hunk(&mut self, file_idx: usize) {
+        if let Some(file) = self.files.get(file_idx) {
+            if let Some(next) = file
+                .decisions
+                .iter()
+                .position(Option::is_none)
+            {
+                self.selection = Some((file_idx, next));
+            }
+        }
+    }
+
+    fn finalize_file(&mut self, file_idx: usize) {
+        if let Some(file) = self.files.get(file_idx) {
+            let content = file.resolved_content();
+
+            // every hunk got rejected: nothing to write or stage
+            if content != file.result.old_content {
+                if let Err(e) = apply_replace(
+                    CWD,
+                    &file.result.path,
+                    &content,
+                ) {
+                    error!("failed writing replacement: {}", e);
+                    return;
+                }
+
+                sync::stage_add(
+                    CWD,
+                    Path::new(&file.result.path),
+                );
+            }
+        }
+
+        self.files.remove(file_idx);
+        self.clamp_selection();
+
+        self.queue
+            .borrow_mut()
+            .push_back(InternalEvent::Update(NeedsUpdate::ALL));
+    }
+
+    fn clamp_selection(&mut self) {
+        self.selection = if self.files.is_empty() {
+            None
+        } else {
+            let idx =
+                self.selection.map_or(0, |(i, _)| i).min(
+                    self.files.len().saturating_sub(1),
+                );
+            Some((idx, 0))
+        };
+    }
+
+    fn move_selection(&mut self, delta: i32) {
+        if let Some((file_idx, hunk_idx)) = self.selection {
+            let max_hunk = self.files[file_idx].hunks.len();
+            let next = hunk_idx as i32 + delta;
+
+            self.selection = Some(if next < 0 {
+                (file_idx, hunk_idx)
+            } else if (next as usize) < max_hunk {
+                (file_idx, next as usize)
+            } else {
+                (file_idx, hunk_idx)
+            });
+        }
+    }
+}
+
+impl DrawableComponent for ReplaceComponent {
+    fn draw<B: Backend>(&self, f: &mut Frame<B>, r: Rect) {
+        if !self.visible {
+            return;
+        }
+
+        let mut lines = Vec::new();
+        lines.push(Text::Raw(Cow::from(format!(
+            "pattern: {}",
+            self.pattern
+        ))));
+        lines.push(Text::Raw(Cow::from(format!(
+            "replace: {}",
+            self.replacement
+        ))));
+
+        if let Some((file_idx, hunk_idx)) = self.selection {
+            if let Some(file) = self.files.get(file_idx) {
+                lines.push(Text::Raw(Cow::from(format!(
+                    "{}",
+                    file.result.path.display()
+                ))));
+
+                if let Some(hunk) = file.hunks.get(hunk_idx) {
+                    for line in &hunk.diff.lines {
+                        let style = match line.line_type {
+                            sync::diff::DiffLineType::Add => {
+                                Style::default().fg(Color::Green)
+                            }
+                            sync::diff::DiffLineType::Delete => {
+                                Style::default().fg(Color::Red)
+                            }
+                            _ => Style::default(),
+                        };
+                        lines.push(Text::Styled(
+                            Cow::from(line.content.clone()),
+                            style,
+                        ));
+                    }
+                }
+            }
+        }
+
+        ui::draw_list(
+            f,
+            r,
+            &strings::REPLACE_TITLE.to_string(),
+            lines.into_iter(),
+            None,
+            true,
+        );
+    }
+}
+
+impl Component for ReplaceComponent {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        _force_all: bool,
+    ) -> CommandBlocking {
+        out.push(CommandInfo::new(
+            commands::REPLACE_OPEN,
+            true,
+            !self.visible,
+        ));
+        out.push(CommandInfo::new(
+            commands::REPLACE_ACCEPT,
+            self.selection.is_some(),
+            self.visible,
+        ));
+        out.push(CommandInfo::new(
+            commands::REPLACE_REJECT,
+            self.selection.is_some(),
+            self.visible,
+        ));
+        out.push(CommandInfo::new(
+            commands::CLOSE_POPUP,
+            true,
+            self.visible,
+        ));
+        visibility_blocking(self)
+    }
+
+    fn event(&mut self, ev: Event) -> bool {
+        if !self.visible {
+            if let Event::Key(e) = ev {
+                if let keys::OPEN_REPLACE = e {
+                    self.show();
+                    return true;
+                }
+            }
+            return false;
+        }
+
+        if let Event::Key(e) = ev {
+            // once a search produced results, keys navigate and
+            // accept/reject hunks instead of typing into the
+            // pattern/replacement fields
+            if !self.files.is_empty() {
+                match e.code {
+                    KeyCode::Esc => self.hide(),
+                    KeyCode::Down => self.move_selection(1),
+                    KeyCode::Up => self.move_selection(-1),
+                    KeyCode::Char('a') => self.accept_selected(),
+                    KeyCode::Char('r') => self.reject_selected(),
+                    _ => (),
+                }
+                return true;
+            }
+
+            match e.code {
+                KeyCode::Esc => self.hide(),
+                KeyCode::Tab => {
+                    self.editing_replacement =
+                        !self.editing_replacement;
+                }
+                KeyCode::Enter => self.run_search(),
+                KeyCode::Char(c) => {
+                    if self.editing_replacement {
+                        self.replacement.push(c);
+                    } else {
+                        self.pattern.push(c);
+                    }
+                }
+                KeyCode::Backspace => {
+                    if self.editing_replacement {
+                        self.replacement.pop();
+                    } else {
+                        self.pattern.pop();
+                    }
+                }
+                _ => (),
+            }
+            return true;
+        }
+
+        false
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false
+    }
+
+    fn show(&mut self) {
+        self.visible = true
+    }
+}
@@ -0,0 +1,171 @@
+use asyncgit::sync::diff::{DiffLine, DiffLineType};
+use lru::LruCache;
+use once_cell::sync::Lazy;
+use std::{num::NonZeroUsize, path::Path, sync::Mutex};
+use syntect::{
+    easy::HighlightLines,
+    highlighting::{
+        Color as SyntectColor, Style as SyntectStyle, ThemeSet,
+    },
+    parsing::SyntaxSet,
+};
+use tui::{
+    style::{Color, Style},
+    widgets::Text,
+};
+
+static SYNTAX_SET: Lazy<SyntaxSet> =
+    Lazy::new(SyntaxSet::load_defaults_newlines);
+static THEME_SET: Lazy<ThemeSet> = Lazy::new(ThemeSet::load_defaults);
+
+const COLOR_ADDED_BG: Color = Color::Rgb(0, 40, 0);
+const COLOR_REMOVED_BG: Color = Color::Rgb(40, 0, 0);
+
+fn syntect_color_to_tui(c: SyntectColor) -> Color {
+    Color::Rgb(c.r, c.g, c.b)
+}
+
+fn line_background(line_type: DiffLineType) -> Option<Color> {
+    match line_type {
+        DiffLineType::Add => Some(COLOR_ADDED_BG),
+        DiffLineType::Delete => Some(COLOR_REMOVED_BG),
+        _ => None,
+    }
+}
+
+// keeps at most this many highlighted files around; a user browsing
+// a diff rarely has more than a handful of files open at once
+const CACHE_CAPACITY: usize = 32;
+
+/// highlights diff content by language, keeping a small LRU cache so
+/// scrolling through an already-highlighted file is free
+pub struct SyntaxHighlighter {
+    cache: Mutex<LruCache<(String, u64), Vec<Vec<Text<'static>>>>>,
+    enabled: bool,
+}
+
+impl SyntaxHighlighter {
+    ///
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            cache: Mutex::new(LruCache::new(
+                NonZeroUsize::new(CACHE_CAPACITY).unwrap(),
+            )),
+            enabled,
+        }
+    }
+
+    /// highlight `lines` from `path`, falling back to the plain
+    /// add/remove coloring when syntax highlighting is disabled or
+    /// no syntax is found for the file extension
+    pub fn highlight(
+        &self,
+        path: &str,
+        content_hash: u64,
+        lines: &[DiffLine],
+    ) -> Vec<Vec<Text<'static>>> {
+        if !self.enabled {
+            return lines.iter().map(plain_line).collect();
+        }
+
+        let key = (path.to_string(), content_hash);
+
+        if let Some(cached) = self.cache.lock().unwrap().get(&key) {
+            return cached.clone();
+        }
+
+        let highlighted = highlight_lines(path, lines)
+            .unwrap_or_else(|| {
+                lines.iter().map(plain_line).collect()
+            });
+
+        self.cache
+            .lock()
+            .unwrap()
+            .put(key, highlighted.clone());
+
+        highlighted
+    }
+}
+
+fn plain_line(line: &DiffLine) -> Vec<Text<'static>> {
+    let fg = match line.line_type {
+        DiffLineType::Add => Color::Green,
+        DiffLineType::Delete => Color::Red,
+        _ => Color::White,
+    };
+
+    vec![Text::Styled(
+        line.content.clone().into(),
+        Style::default().fg(fg),
+    )]
+}
+
+fn highlight_lines(
+    path: &str,
+    lines: &[DiffLine],
+) -> Option<Vec<Vec<Text<'static>>>> {
+    let extension =
+        Path::new(path).extension()?.to_str().unwrap_or_default();
+
+    let syntax =
+        SYNTAX_SET.find_syntax_by_extension(extension)?;
+
+    let theme = &THEME_SET.themes["base16-ocean.dark"];
+    let mut highlighter = HighlightLines::new(syntax, theme);
+
+    Some(
+        lines
+            .iter()
+            .map(|line| {
+                // `line.content` has the diff marker ('+'/'-'/' ')
+                // glued onto the real source text; feeding that
+                // straight into syntect pollutes the first token of
+                // every line, so split it off and highlight only the
+                // actual source text
+                let mut chars = line.content.chars();
+                let marker = chars.next();
+                let source = chars.as_str();
+
+                let background = line_background(line.line_type);
+
+                let ranges: Vec<(SyntectStyle, &str)> =
+                    highlighter.highlight(source, &SYNTAX_SET);
+
+                let mut spans = Vec::with_capacity(ranges.len() + 1);
+
+                if let Some(marker) = marker {
+                    let mut marker_style = Style::default();
+                    if let Some(bg) = background {
+                        marker_style = marker_style.bg(bg);
+                    }
+                    spans.push(Text::Styled(
+                        marker.to_string().into(),
+                        marker_style,
+                    ));
+                }
+
+                spans.extend(ranges.into_iter().map(
+                    |(style, token)| {
+                        let mut tui_style = Style::default().fg(
+                            syntect_color_to_tui(
+                                style.foreground,
+                            ),
+                        );
+
+                        if let Some(bg) = background {
+                            tui_style = tui_style.bg(bg);
+                        }
+
+                        Text::Styled(
+                            token.to_string().into(),
+                            tui_style,
+                        )
+                    },
+                ));
+
+                spans
+            })
+            .collect(),
+    )
+}
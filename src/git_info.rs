@@ -0,0 +1,64 @@
+use crate::queue::{InternalEvent, Queue};
+use asyncgit::sync::state::{repo_state, RepoState};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::thread;
+
+/// computes [`RepoState`] on a background thread and delivers it to
+/// the main loop via `InternalEvent::GitInfo`, using the same
+/// channel hand-off as [`crate::watcher::RepoWatcher`] (see there for
+/// why `Queue` can't cross the thread boundary directly) - call
+/// [`AsyncGitInfo::update`] from the main thread to drain it into the
+/// real `Queue`.
+pub struct AsyncGitInfo {
+    last: RepoState,
+    sender: Sender<RepoState>,
+    receiver: Receiver<RepoState>,
+}
+
+impl AsyncGitInfo {
+    ///
+    pub fn new() -> Self {
+        let (sender, receiver) = channel();
+
+        Self {
+            last: RepoState::default(),
+            sender,
+            receiver,
+        }
+    }
+
+    /// last known state, available immediately without blocking
+    pub fn last(&self) -> RepoState {
+        self.last.clone()
+    }
+
+    /// drains any state computed since the last call, caching it
+    /// and pushing `InternalEvent::GitInfo` onto `queue`; call this
+    /// regularly from the main/render thread
+    pub fn update(&mut self, queue: &Queue) {
+        while let Ok(state) = self.receiver.try_recv() {
+            self.last = state.clone();
+            queue
+                .borrow_mut()
+                .push_back(InternalEvent::GitInfo(state));
+        }
+    }
+
+    /// kicks off a recompute on a background thread; call this from
+    /// the filesystem watcher and other refresh triggers
+    pub fn fetch(&self, repo_path: &str) {
+        let sender = self.sender.clone();
+        let repo_path = repo_path.to_string();
+
+        thread::spawn(move || {
+            let state = repo_state(&repo_path);
+            let _ = sender.send(state);
+        });
+    }
+}
+
+impl Default for AsyncGitInfo {
+    fn default() -> Self {
+        Self::new()
+    }
+}
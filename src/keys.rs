@@ -7,6 +7,13 @@ const fn no_mod(code: KeyCode) -> KeyEvent {
     }
 }
 
+const fn ctrl(code: KeyCode) -> KeyEvent {
+    KeyEvent {
+        code,
+        modifiers: KeyModifiers::CONTROL,
+    }
+}
+
 pub const FOCUS_WORKDIR: KeyEvent = no_mod(KeyCode::Char('1'));
 pub const FOCUS_STAGE: KeyEvent = no_mod(KeyCode::Char('2'));
 pub const FOCUS_RIGHT: KeyEvent = no_mod(KeyCode::Right);
@@ -19,5 +26,12 @@ pub const EXIT_2: KeyEvent = no_mod(KeyCode::Char('q'));
 pub const CLOSE_MSG: KeyEvent = no_mod(KeyCode::Enter);
 pub const OPEN_COMMIT: KeyEvent = no_mod(KeyCode::Char('c'));
 pub const OPEN_HELP: KeyEvent = no_mod(KeyCode::Char('h'));
+pub const OPEN_REPLACE: KeyEvent = no_mod(KeyCode::Char('R'));
+// plain terminals (without the Kitty keyboard-enhancement protocol,
+// which nothing here enables) report Ctrl+Enter as indistinguishable
+// from a bare Enter, so the "commit" key has to be a Ctrl+letter
+// combo to be reachable at all
+pub const COMMIT_CONFIRM: KeyEvent = ctrl(KeyCode::Char('s'));
+pub const COMMIT_OPEN_EDITOR: KeyEvent = ctrl(KeyCode::Char('e'));
 pub const MOVE_UP: KeyEvent = no_mod(KeyCode::Up);
 pub const MOVE_DOWN: KeyEvent = no_mod(KeyCode::Down);
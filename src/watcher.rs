@@ -0,0 +1,129 @@
+use crate::queue::{InternalEvent, NeedsUpdate, Queue};
+use log::error;
+use notify::{watcher, DebouncedEvent, RecursiveMode, Watcher as _};
+use std::{
+    path::Path,
+    sync::mpsc::{channel, Receiver, RecvTimeoutError, Sender},
+    thread,
+    time::Duration,
+};
+
+const DEBOUNCE: Duration = Duration::from_millis(100);
+const SHUTDOWN_POLL: Duration = Duration::from_millis(200);
+
+fn is_ignored(event: &DebouncedEvent) -> bool {
+    match event {
+        DebouncedEvent::Chmod(p)
+        | DebouncedEvent::Create(p)
+        | DebouncedEvent::Write(p)
+        | DebouncedEvent::Remove(p)
+        | DebouncedEvent::Rename(p, _) => {
+            let p = p.to_string_lossy();
+            p.contains(".git/objects")
+                || p.contains(".git/index.lock")
+                || p.contains(".git/FETCH_HEAD")
+        }
+        // these fire ahead of debouncing purely as a heads-up, or
+        // carry no useful path - never worth a refresh on their own
+        DebouncedEvent::NoticeWrite(_)
+        | DebouncedEvent::NoticeRemove(_)
+        | DebouncedEvent::Rescan
+        | DebouncedEvent::Error(..) => true,
+    }
+}
+
+/// watches the working directory and `.git` for changes on a
+/// background thread. `Queue` is `Rc<RefCell<_>>` and therefore not
+/// `Send`, so the watcher thread only ever talks to the main thread
+/// through an `InternalEvent` channel - call [`RepoWatcher::update`]
+/// from the main loop to drain it into the real `Queue`.
+pub struct RepoWatcher {
+    events: Receiver<InternalEvent>,
+    shutdown: Sender<()>,
+    handle: Option<thread::JoinHandle<()>>,
+}
+
+impl RepoWatcher {
+    ///
+    pub fn new(repo_path: &str) -> Self {
+        let (event_tx, event_rx) = channel();
+        let (shutdown_tx, shutdown_rx) = channel();
+        let repo_path = repo_path.to_string();
+
+        let handle = thread::spawn(move || {
+            if let Err(e) =
+                watch_loop(&repo_path, &event_tx, &shutdown_rx)
+            {
+                error!("fs watcher error: {}", e);
+            }
+        });
+
+        Self {
+            events: event_rx,
+            shutdown: shutdown_tx,
+            handle: Some(handle),
+        }
+    }
+
+    /// drains any pending filesystem-change notifications into
+    /// `queue`; call this regularly from the main/render thread
+    pub fn update(&self, queue: &Queue) {
+        while let Ok(event) = self.events.try_recv() {
+            queue.borrow_mut().push_back(event);
+        }
+    }
+}
+
+impl Drop for RepoWatcher {
+    fn drop(&mut self) {
+        // tell the background thread to stop, then wait for it so
+        // we never leak the watcher thread
+        let _ = self.shutdown.send(());
+
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+fn watch_loop(
+    repo_path: &str,
+    events: &Sender<InternalEvent>,
+    shutdown: &Receiver<()>,
+) -> Result<(), notify::Error> {
+    let (tx, rx) = channel();
+
+    let mut watcher = watcher(tx, DEBOUNCE)?;
+    watcher.watch(repo_path, RecursiveMode::Recursive)?;
+    watcher.watch(
+        Path::new(repo_path).join(".git"),
+        RecursiveMode::Recursive,
+    )?;
+
+    loop {
+        if shutdown.try_recv().is_ok() {
+            return Ok(());
+        }
+
+        match rx.recv_timeout(SHUTDOWN_POLL) {
+            Ok(DebouncedEvent::Error(e, path)) => {
+                error!("fs watcher event error: {} ({:?})", e, path);
+                continue;
+            }
+            Ok(event) => {
+                if is_ignored(&event) {
+                    continue;
+                }
+
+                if events
+                    .send(InternalEvent::Update(NeedsUpdate::ALL))
+                    .is_err()
+                {
+                    return Ok(());
+                }
+            }
+            Err(RecvTimeoutError::Timeout) => continue,
+            Err(RecvTimeoutError::Disconnected) => return Ok(()),
+        }
+    }
+}